@@ -1,5 +1,9 @@
 //! The main Determinishtic struct that wraps a ConnectTo component.
 
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+
 use sacp::{
     Agent, Client, ConnectionTo, ConnectTo,
     role::{HasPeer, Role},
@@ -8,12 +12,130 @@ use sacp::{
 use sacp_conductor::{AgentOnly, ConductorImpl, McpBridgeMode};
 use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
-use tokio::sync::oneshot;
+use tokio::sync::{broadcast, oneshot, watch};
 use tokio::task::JoinHandle;
-use tracing::{debug, info, instrument};
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+use tracing::{debug, info, instrument, warn};
 
 use crate::ThinkBuilder;
 
+/// Default capacity of the [`WireEvent`] broadcast channel; see
+/// [`ReconnectConfig::events_capacity`] to configure it for a supervised connection.
+const DEFAULT_EVENTS_CAPACITY: usize = 1024;
+
+/// Tracks the timestamp of the last observed activity on a connection.
+///
+/// [`ThinkBuilder`](crate::ThinkBuilder) touches this whenever it sends a prompt or
+/// receives agent traffic; [`supervise`] consults it instead of probing on a fixed
+/// schedule regardless of real usage.
+pub(crate) struct LastActivity(StdMutex<Instant>);
+
+impl LastActivity {
+    fn new() -> Self {
+        Self(StdMutex::new(Instant::now()))
+    }
+
+    pub(crate) fn touch(&self) {
+        *self.0.lock().expect("last-activity mutex poisoned") = Instant::now();
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.0.lock().expect("last-activity mutex poisoned").elapsed()
+    }
+}
+
+/// An event observed on the wire between this process and the agent.
+///
+/// Delivered to every [`Determinishtic::subscribe`] receiver, independent of any
+/// particular [`think`](Determinishtic::think) call - useful for a logging sink, a
+/// metrics exporter, or a debugger UI that wants to observe traffic without
+/// interfering with think-block results.
+#[derive(Debug, Clone)]
+pub enum WireEvent {
+    /// A prompt was sent to the agent.
+    PromptSent { prompt: String },
+    /// Incoming assistant text.
+    AssistantText(String),
+    /// An MCP tool was dispatched.
+    ToolDispatched { name: String },
+    /// The agent's response failed to deserialize into the expected `Output` type.
+    DeserializationFailed { error: String },
+}
+
+/// How to space successive reconnect attempts once a link is judged dead.
+#[derive(Debug, Clone)]
+pub enum ReconnectStrategy {
+    /// Always wait the same interval between attempts.
+    FixedInterval(Duration),
+    /// Wait `initial`, then `initial + increment`, `initial + 2*increment`, ...,
+    /// capped at `max`.
+    LinearBackoff {
+        initial: Duration,
+        increment: Duration,
+        max: Duration,
+    },
+    /// Wait `initial`, then `initial * factor`, `initial * factor^2`, ..., capped at
+    /// `max`.
+    ExponentialBackoff {
+        initial: Duration,
+        factor: f64,
+        max: Duration,
+    },
+}
+
+impl ReconnectStrategy {
+    fn delay(&self, attempt: u32) -> Duration {
+        match self {
+            ReconnectStrategy::FixedInterval(interval) => *interval,
+            ReconnectStrategy::LinearBackoff { initial, increment, max } => {
+                (*initial + *increment * attempt).min(*max)
+            }
+            ReconnectStrategy::ExponentialBackoff { initial, factor, max } => {
+                // A long outage can drive `attempt` high enough that `scaled` no
+                // longer fits in the range `Duration::from_secs_f64` accepts, well
+                // before the `.min(max)` below would ever clamp it. Saturate to
+                // `max` instead of letting that conversion panic.
+                let exponent = i32::try_from(attempt).unwrap_or(i32::MAX);
+                let scaled = initial.as_secs_f64() * factor.powi(exponent);
+                if scaled.is_finite() && scaled <= Duration::MAX.as_secs_f64() {
+                    Duration::from_secs_f64(scaled).min(*max)
+                } else {
+                    *max
+                }
+            }
+        }
+    }
+}
+
+/// Configuration for supervised reconnection, used with [`Determinishtic::new_with_config`].
+#[derive(Debug, Clone)]
+pub struct ReconnectConfig {
+    /// How to space successive reconnect attempts.
+    pub strategy: ReconnectStrategy,
+    /// How long the link may go quiet (no activity, including keep-alive frames)
+    /// before it's probed with a handshake; if that probe fails, the link is treated
+    /// as dead and reconnection begins.
+    pub max_silence: Duration,
+    /// Capacity of the [`WireEvent`] broadcast channel backing [`Determinishtic::subscribe`].
+    /// A subscriber that falls behind this many events misses the oldest ones rather
+    /// than blocking the connection.
+    pub events_capacity: usize,
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            strategy: ReconnectStrategy::ExponentialBackoff {
+                initial: Duration::from_millis(200),
+                factor: 2.0,
+                max: Duration::from_secs(30),
+            },
+            max_silence: Duration::from_secs(30),
+            events_capacity: DEFAULT_EVENTS_CAPACITY,
+        }
+    }
+}
+
 /// The main entry point for determinishtic operations.
 ///
 /// Wraps a sacp [`ConnectionTo`] component and provides the [`think`](Self::think) method
@@ -24,14 +146,17 @@ use crate::ThinkBuilder;
 /// When using an existing connection (e.g., from inside a proxy), use [`from_connection`](Self::from_connection)
 /// which accepts any role that can communicate with an agent.
 ///
-/// When created via `new`, the connection runs in a background task and is cancelled
-/// when `Determinishtic` is dropped.
+/// When created via `new` or `new_with_config`, the connection runs in a background
+/// task and is cancelled when `Determinishtic` is dropped.
 pub struct Determinishtic<R: Role = Agent>
 where
     R: HasPeer<Agent>,
 {
-    cx: ConnectionTo<R>,
-    task: Option<JoinHandle<Result<(), sacp::Error>>>,
+    cx: watch::Receiver<ConnectionTo<R>>,
+    task: Option<JoinHandle<()>>,
+    reconnecting: Arc<AtomicBool>,
+    events: broadcast::Sender<WireEvent>,
+    activity: Arc<LastActivity>,
 }
 
 impl<R: Role> Determinishtic<R>
@@ -50,26 +175,119 @@ where
     /// // Inside an MCP tool handler
     /// async fn my_tool(cx: McpConnectionTo<Conductor>) -> Result<Output, Error> {
     ///     let d = Determinishtic::from_connection(cx.connection_to());
-    ///     let result: MyOutput = d.think()
+    ///     let result: MyOutput = d.think().await?
     ///         .text("Do something")
+    ///         .run()
     ///         .await?;
     ///     Ok(result)
     /// }
     /// ```
     pub fn from_connection(cx: ConnectionTo<R>) -> Self {
-        Self { cx, task: None }
+        let (_tx, rx) = watch::channel(cx);
+        let (events, _) = broadcast::channel(DEFAULT_EVENTS_CAPACITY);
+        Self {
+            cx: rx,
+            task: None,
+            reconnecting: Arc::new(AtomicBool::new(false)),
+            events,
+            activity: Arc::new(LastActivity::new()),
+        }
     }
 
     /// Start building a think block.
     ///
     /// Returns a [`ThinkBuilder`] that can be used to compose the prompt
     /// and register tools. The builder is consumed when awaited.
-    pub fn think<'bound, Output>(&self) -> ThinkBuilder<'bound, Output, R>
+    ///
+    /// Resolves through [`connection`](Self::connection), so a call made while a
+    /// reconnect is underway awaits the fresh connection (or fails with
+    /// [`Error::Reconnecting`](crate::Error::Reconnecting)) instead of being handed
+    /// a connection that's about to be torn down.
+    pub async fn think<'bound, Output>(&self) -> Result<ThinkBuilder<'bound, Output, R>, crate::Error>
     where
         Output: Send + JsonSchema + DeserializeOwned + 'static,
     {
-        ThinkBuilder::new(self.cx.clone())
+        let cx = self.connection().await?;
+        Ok(ThinkBuilder::new(cx, self.events.clone(), self.activity.clone()))
+    }
+
+    /// Subscribe to every [`WireEvent`] flowing over this connection.
+    ///
+    /// Independent from [`think`](Self::think)'s results: a logging sink, a metrics
+    /// exporter, and a debugger UI can each subscribe and receive their own cloned
+    /// stream of events, including deserialization failures that would otherwise
+    /// only surface as a generic `serde_json` error
+    /// ([`Error::Deserialization`](crate::Error::Deserialization)).
+    pub fn subscribe(&self) -> impl Stream<Item = WireEvent> {
+        BroadcastStream::new(self.events.subscribe()).filter_map(|event| event.ok())
+    }
+
+    /// The current connection, awaiting a freshly reconnected one if a reconnect is
+    /// underway rather than handing back a connection that's about to be torn down.
+    ///
+    /// [`think`](Self::think) calls this instead of reading the raw watch channel
+    /// directly, so a new think block started mid-reconnect observes the fresh
+    /// connection rather than failing with a generic closed-connection error.
+    pub(crate) async fn connection(&self) -> Result<ConnectionTo<R>, crate::Error> {
+        if self.reconnecting.load(Ordering::Acquire) {
+            let mut rx = self.cx.clone();
+            rx.changed().await.map_err(|_| crate::Error::Reconnecting)?;
+            return Ok(rx.borrow().clone());
+        }
+        Ok(self.cx.borrow().clone())
     }
+
+    /// Re-run the MCP-over-ACP handshake to confirm the connection is still usable.
+    ///
+    /// Used by [`PatchworkPool`](crate::PatchworkPool) to validate a connection before
+    /// handing it back out for reuse.
+    pub(crate) async fn health_check(&self) -> Result<(), crate::Error> {
+        let InitializeResponse { .. } = self
+            .cx
+            .borrow()
+            .clone()
+            .send_request(InitializeRequest::new(ProtocolVersion::LATEST))
+            .block_task()
+            .await?;
+        Ok(())
+    }
+}
+
+/// Connect `component` and run the MCP-over-ACP handshake, returning the established
+/// connection and the task that drives it until the link closes.
+async fn connect(
+    component: impl ConnectTo<Client> + 'static,
+) -> Result<(ConnectionTo<Agent>, JoinHandle<()>), crate::Error> {
+    let (tx, rx) = oneshot::channel();
+
+    let task = tokio::spawn(async move {
+        let result = Client
+            .builder()
+            .with_spawned(|cx| async move {
+                // Send the connection context back to the caller
+                let _ = tx.send(cx);
+                // Keep running until the connection closes
+                std::future::pending::<Result<(), sacp::Error>>().await
+            })
+            .connect_to(ConductorImpl::new_agent(
+                "determinishtic-conductor",
+                AgentOnly(component),
+                McpBridgeMode::default(),
+            ))
+            .await;
+
+        if let Err(err) = result {
+            debug!(%err, "connection task ended");
+        }
+    });
+
+    let cx = rx.await.map_err(|_| crate::Error::ConnectionClosed)?;
+
+    // FIXME: we should check that it supports MCP-over-ACP
+    let InitializeResponse { .. } =
+        cx.send_request(InitializeRequest::new(ProtocolVersion::LATEST)).block_task().await?;
+
+    Ok((cx, task))
 }
 
 impl Determinishtic<Agent> {
@@ -78,42 +296,129 @@ impl Determinishtic<Agent> {
     /// This spawns a background task to run the connection to the agent.
     /// The component will be used to communicate with an LLM agent.
     ///
+    /// This does not supervise the link for drops - a lost connection surfaces as
+    /// a failed request ([`Error::Connection`](crate::Error::Connection)). For automatic
+    /// reconnection, use [`new_with_config`](Self::new_with_config).
+    ///
     /// For use inside proxies where you already have a connection, use
     /// [`from_connection`](Self::from_connection) instead.
     #[instrument(name = "Determinishtic::new", skip_all)]
-    pub async fn new(
-        component: impl ConnectTo<Client> + 'static,
-    ) -> Result<Self, crate::Error> {
+    pub async fn new(component: impl ConnectTo<Client> + 'static) -> Result<Self, crate::Error> {
         debug!("spawning connection task");
-        let (tx, rx) = oneshot::channel();
+        let (cx, task) = connect(component).await?;
+        info!("connection established");
 
-        let task = tokio::spawn(async move {
-            Client
-                .builder()
-                .with_spawned(|cx| async move {
-                    // Send the connection context back to the caller
-                    let _ = tx.send(cx);
-                    // Keep running until the connection closes
-                    std::future::pending::<Result<(), sacp::Error>>().await
-                })
-                .connect_to(ConductorImpl::new_agent(
-                    "determinishtic-conductor",
-                    AgentOnly(component),
-                    McpBridgeMode::default(),
-                ))
-                .await
-        });
+        let (_tx, rx) = watch::channel(cx);
+        let (events, _) = broadcast::channel(DEFAULT_EVENTS_CAPACITY);
+        Ok(Self {
+            cx: rx,
+            task: Some(task),
+            reconnecting: Arc::new(AtomicBool::new(false)),
+            events,
+            activity: Arc::new(LastActivity::new()),
+        })
+    }
 
-        let cx = rx.await.map_err(|_| crate::Error::ConnectionClosed)?;
+    /// Create a new Determinishtic instance with a supervised, auto-reconnecting
+    /// connection.
+    ///
+    /// The background task tracks how long the link has gone quiet; once it exceeds
+    /// `config.max_silence` the connection is probed with a handshake, and if that
+    /// fails it is treated as dead and re-established using `config.strategy` to
+    /// space attempts, re-running the MCP-over-ACP handshake each time. Every
+    /// connection attempt, including the first, uses a clone of `component`.
+    #[instrument(name = "Determinishtic::new_with_config", skip_all)]
+    pub async fn new_with_config(
+        component: impl ConnectTo<Client> + Clone + 'static,
+        config: ReconnectConfig,
+    ) -> Result<Self, crate::Error> {
+        debug!("spawning connection task");
+        let (cx, task) = connect(component.clone()).await?;
         info!("connection established");
 
-        // FIXME: we should check that it supports MCP-over-ACP
-        let InitializeResponse { .. } = cx
-            .send_request(InitializeRequest::new(ProtocolVersion::LATEST))
-            .block_task()
-            .await?;
+        let reconnecting = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = watch::channel(cx);
+        let (events, _) = broadcast::channel(config.events_capacity);
+        let activity = Arc::new(LastActivity::new());
+
+        let supervisor = tokio::spawn(supervise(
+            component,
+            config,
+            tx,
+            task,
+            reconnecting.clone(),
+            activity.clone(),
+        ));
+
+        Ok(Self { cx: rx, task: Some(supervisor), reconnecting, events, activity })
+    }
+}
+
+/// Watch the live connection for silence and reconnect when it goes quiet.
+async fn supervise(
+    component: impl ConnectTo<Client> + Clone + 'static,
+    config: ReconnectConfig,
+    tx: watch::Sender<ConnectionTo<Agent>>,
+    initial_task: JoinHandle<()>,
+    reconnecting: Arc<AtomicBool>,
+    activity: Arc<LastActivity>,
+) {
+    // Aborts the live connection task when the supervisor itself is aborted (on
+    // `Determinishtic` drop); a bare `JoinHandle` would only detach it.
+    struct AbortOnDrop(JoinHandle<()>);
+    impl Drop for AbortOnDrop {
+        fn drop(&mut self) {
+            self.0.abort();
+        }
+    }
+    let mut current_task = AbortOnDrop(initial_task);
+
+    loop {
+        // Wait until `activity` has genuinely been quiet for `max_silence`. Real
+        // think-block traffic resets the timer, so a busy link is never probed;
+        // only once it's actually been silent that long do we verify with a
+        // lightweight handshake before declaring it dead.
+        loop {
+            let quiet_for = activity.elapsed();
+            if quiet_for < config.max_silence {
+                tokio::time::sleep(config.max_silence - quiet_for).await;
+                continue;
+            }
+
+            let cx = tx.borrow().clone();
+            let alive = cx
+                .send_request(InitializeRequest::new(ProtocolVersion::LATEST))
+                .block_task()
+                .await
+                .is_ok();
+            if alive {
+                activity.touch();
+                continue;
+            }
+            break;
+        }
 
-        Ok(Self { cx, task: Some(task) })
+        warn!(max_silence = ?config.max_silence, "link silent past threshold, reconnecting");
+        reconnecting.store(true, Ordering::Release);
+
+        let mut attempt = 0u32;
+        loop {
+            match connect(component.clone()).await {
+                Ok((cx, task)) => {
+                    info!(attempt, "reconnected");
+                    current_task = AbortOnDrop(task);
+                    let _ = tx.send(cx);
+                    activity.touch();
+                    reconnecting.store(false, Ordering::Release);
+                    break;
+                }
+                Err(err) => {
+                    warn!(attempt, %err, "reconnect attempt failed");
+                    tokio::time::sleep(config.strategy.delay(attempt)).await;
+                    attempt += 1;
+                }
+            }
+        }
     }
 }
 
@@ -127,3 +432,65 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_interval_ignores_attempt() {
+        let strategy = ReconnectStrategy::FixedInterval(Duration::from_secs(1));
+        assert_eq!(strategy.delay(0), Duration::from_secs(1));
+        assert_eq!(strategy.delay(10), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn linear_backoff_increases_then_caps() {
+        let strategy = ReconnectStrategy::LinearBackoff {
+            initial: Duration::from_millis(100),
+            increment: Duration::from_millis(100),
+            max: Duration::from_millis(250),
+        };
+        assert_eq!(strategy.delay(0), Duration::from_millis(100));
+        assert_eq!(strategy.delay(1), Duration::from_millis(200));
+        assert_eq!(strategy.delay(2), Duration::from_millis(250));
+        assert_eq!(strategy.delay(10), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn exponential_backoff_survives_long_outage_without_overflowing_duration() {
+        let strategy = ReconnectStrategy::ExponentialBackoff {
+            initial: Duration::from_millis(200),
+            factor: 2.0,
+            max: Duration::from_secs(30),
+        };
+        // `attempt` well past the point where `initial * factor.powi(attempt)`
+        // overflows what `Duration::from_secs_f64` accepts - this must saturate to
+        // `max` instead of panicking.
+        assert_eq!(strategy.delay(67), Duration::from_secs(30));
+        assert_eq!(strategy.delay(u32::MAX), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn exponential_backoff_doubles_then_caps() {
+        let strategy = ReconnectStrategy::ExponentialBackoff {
+            initial: Duration::from_millis(100),
+            factor: 2.0,
+            max: Duration::from_secs(1),
+        };
+        assert_eq!(strategy.delay(0), Duration::from_millis(100));
+        assert_eq!(strategy.delay(1), Duration::from_millis(200));
+        assert_eq!(strategy.delay(2), Duration::from_millis(400));
+        assert_eq!(strategy.delay(10), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn last_activity_touch_resets_elapsed() {
+        let activity = LastActivity::new();
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(activity.elapsed() >= Duration::from_millis(20));
+
+        activity.touch();
+        assert!(activity.elapsed() < Duration::from_millis(20));
+    }
+}
@@ -24,4 +24,9 @@ pub enum Error {
     /// The connection was closed before we could get the context.
     #[error("connection closed")]
     ConnectionClosed,
+
+    /// The connection dropped out and is being re-established; retry once
+    /// reconnection completes.
+    #[error("connection is reconnecting")]
+    Reconnecting,
 }
@@ -13,7 +13,7 @@
 //! let patchwork = Patchwork::new(component).await?;
 //!
 //! let name = "Alice";
-//! let result: String = patchwork.think()
+//! let result: String = patchwork.think().await?
 //!     .text("Say hello to")
 //!     .display(&name)
 //!     .text("in a friendly way.")
@@ -21,10 +21,14 @@
 //!     .await?;
 //! ```
 
+mod determinishtic;
 mod error;
 mod patchwork;
+mod pool;
 mod think;
 
+pub use determinishtic::{Determinishtic, ReconnectConfig, ReconnectStrategy, WireEvent};
 pub use error::Error;
 pub use patchwork::Patchwork;
-pub use think::ThinkBuilder;
+pub use pool::{PatchworkPool, PoolConfig, PoolKey, Pooled};
+pub use think::{ThinkBuilder, ThinkEvent};
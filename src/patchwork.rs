@@ -0,0 +1,65 @@
+//! The public entry point for patchwork: [`Patchwork`].
+
+use sacp::{Agent, Client, ConnectTo};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+
+use crate::pool::{self, PoolConfig, PoolKey, Pooled};
+use crate::{Determinishtic, ThinkBuilder};
+
+enum Inner {
+    Owned(Determinishtic),
+    Pooled(Pooled),
+}
+
+/// Blend deterministic Rust code with LLM-powered reasoning.
+///
+/// See the [crate-level docs](crate) for a usage example.
+pub struct Patchwork {
+    inner: Inner,
+}
+
+impl Patchwork {
+    /// Create a new `Patchwork` instance from a sacp component.
+    ///
+    /// This spawns a dedicated background connection for the lifetime of this
+    /// instance. For many short-lived think blocks against the same agent, prefer
+    /// [`pooled`](Self::pooled).
+    pub async fn new(component: impl ConnectTo<Client> + 'static) -> Result<Self, crate::Error> {
+        Ok(Self { inner: Inner::Owned(Determinishtic::new(component).await?) })
+    }
+
+    /// Create a `Patchwork` instance backed by a shared, process-wide [`PatchworkPool`](crate::PatchworkPool),
+    /// keyed by the component's [`PoolKey::pool_key`].
+    ///
+    /// The pooled connection is checked out when `pooled` is called and held for the
+    /// lifetime of this handle; every `think()` on it uses that same connection, and
+    /// it returns to the idle queue only when this handle is dropped.
+    ///
+    /// The underlying pool is a lazily-created singleton shared by every call to
+    /// `pooled` in the process: `config` is only applied the *first* time this is
+    /// called, anywhere in the process. Later callers' `config` values (different
+    /// `max_connections`, different `idle_timeout`) are ignored once the pool exists;
+    /// the mismatch is only logged via `warn!`. If you need independently configured pools, construct your own
+    /// [`PatchworkPool`](crate::PatchworkPool) and call [`PatchworkPool::acquire`](crate::PatchworkPool::acquire)
+    /// directly instead.
+    pub async fn pooled(
+        component: impl ConnectTo<Client> + PoolKey + 'static,
+        config: PoolConfig,
+    ) -> Result<Self, crate::Error> {
+        Ok(Self { inner: Inner::Pooled(pool::pooled(component, config).await?) })
+    }
+
+    /// Start building a think block.
+    pub async fn think<'bound, Output>(
+        &self,
+    ) -> Result<ThinkBuilder<'bound, Output, Agent>, crate::Error>
+    where
+        Output: Send + JsonSchema + DeserializeOwned + 'static,
+    {
+        match &self.inner {
+            Inner::Owned(d) => d.think().await,
+            Inner::Pooled(p) => p.think().await,
+        }
+    }
+}
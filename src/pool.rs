@@ -0,0 +1,217 @@
+//! A connection pool for [`Determinishtic`], keyed by component identity.
+//!
+//! Spawning a fresh background connection per [`think`](crate::Patchwork::think) call
+//! is wasteful when an application runs many short-lived think blocks against the
+//! same agent. [`PatchworkPool`] keeps a bounded set of warm connections per key
+//! around and hands them out on demand, re-validating each one with a fresh
+//! `InitializeRequest` handshake before it is reused.
+
+use std::collections::{HashMap, VecDeque};
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use sacp::{Client, ConnectTo};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::{debug, warn};
+
+use crate::{Determinishtic, Error};
+
+/// Something that can identify which pool bucket it belongs to.
+///
+/// Implement this on a component to let [`Patchwork::pooled`](crate::Patchwork::pooled)
+/// share connections across calls made against "the same" agent.
+pub trait PoolKey {
+    /// A stable identity for this component, used as the pool's `HashMap` key.
+    fn pool_key(&self) -> String;
+}
+
+/// Configuration for a [`PatchworkPool`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoolConfig {
+    /// Maximum number of live connections held across all keys.
+    pub max_connections: usize,
+    /// How long an idle connection may sit before it's evicted rather than reused.
+    pub idle_timeout: Duration,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self { max_connections: 16, idle_timeout: Duration::from_secs(60) }
+    }
+}
+
+struct Idle {
+    connection: Determinishtic,
+    permit: OwnedSemaphorePermit,
+    since: Instant,
+}
+
+struct Inner {
+    idle: Mutex<HashMap<String, VecDeque<Idle>>>,
+    permits: Arc<Semaphore>,
+    config: PoolConfig,
+}
+
+/// A bounded pool of [`Determinishtic`] connections, keyed by component identity.
+///
+/// Connections are handed out by [`acquire`](Self::acquire) and returned to the idle
+/// queue for their key when the returned [`Pooled`] guard is dropped. Total live
+/// connections across all keys are bounded by a semaphore: once `max_connections`
+/// are checked out, further callers await a permit.
+pub struct PatchworkPool {
+    inner: Arc<Inner>,
+}
+
+impl PatchworkPool {
+    /// Create an empty pool with the given configuration.
+    pub fn new(config: PoolConfig) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                idle: Mutex::new(HashMap::new()),
+                permits: Arc::new(Semaphore::new(config.max_connections)),
+                config,
+            }),
+        }
+    }
+
+    /// Check out a connection for `component`'s pool key, reusing an idle one when a
+    /// healthy, non-expired candidate is available, or spawning a new one otherwise.
+    ///
+    /// An idle connection keeps holding the permit it was created with, so total live
+    /// connections - checked out *and* idle - stay bounded by `max_connections`; a
+    /// permit is only released back to the semaphore when a connection is actually
+    /// evicted or fails its health check. Spawning a brand-new connection is the only
+    /// path that acquires a fresh permit, and it blocks while every permit is held.
+    /// Idle connections are not evicted on a timer: an expired one is only dropped,
+    /// freeing its permit, when a later `acquire` for its own key pops it.
+    pub async fn acquire(
+        &self,
+        component: impl ConnectTo<Client> + PoolKey + 'static,
+    ) -> Result<Pooled, Error> {
+        let key = component.pool_key();
+
+        loop {
+            // Pop one idle candidate per iteration - the lock must not be held
+            // across the health check's `.await` below.
+            let candidate = {
+                let mut idle = self.inner.idle.lock().expect("pool idle mutex poisoned");
+                idle.get_mut(&key).and_then(VecDeque::pop_front)
+            };
+            let Some(candidate) = candidate else { break };
+
+            if candidate.since.elapsed() > self.inner.config.idle_timeout {
+                debug!(key, "evicting idle connection past TTL");
+                continue;
+            }
+            if candidate.connection.health_check().await.is_ok() {
+                debug!(key, "reusing pooled connection");
+                return Ok(Pooled {
+                    key,
+                    connection: Some(candidate.connection),
+                    pool: self.inner.clone(),
+                    permit: Some(candidate.permit),
+                });
+            }
+            debug!(key, "discarding connection that failed health check");
+        }
+
+        debug!(key, "no reusable connection, acquiring a permit to spawn a new one");
+        let permit = self
+            .inner
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("pool semaphore is never closed");
+        let connection = Determinishtic::new(component).await?;
+        Ok(Pooled {
+            key,
+            connection: Some(connection),
+            pool: self.inner.clone(),
+            permit: Some(permit),
+        })
+    }
+}
+
+/// A checked-out pooled connection.
+///
+/// Derefs to [`Determinishtic`]. Returned to the idle queue for reuse when dropped,
+/// carrying its semaphore permit along with it - the permit bounds total *live*
+/// connections, not just checked-out ones, so it stays held for as long as the
+/// connection does and is only released when the connection is evicted or fails a
+/// health check in [`PatchworkPool::acquire`].
+pub struct Pooled {
+    key: String,
+    connection: Option<Determinishtic>,
+    pool: Arc<Inner>,
+    permit: Option<OwnedSemaphorePermit>,
+}
+
+impl Deref for Pooled {
+    type Target = Determinishtic;
+    fn deref(&self) -> &Determinishtic {
+        self.connection.as_ref().expect("connection taken before drop")
+    }
+}
+
+impl DerefMut for Pooled {
+    fn deref_mut(&mut self) -> &mut Determinishtic {
+        self.connection.as_mut().expect("connection taken before drop")
+    }
+}
+
+impl Drop for Pooled {
+    fn drop(&mut self) {
+        let Some(connection) = self.connection.take() else { return };
+        let permit = self.permit.take().expect("permit is only taken alongside connection");
+        // Requeue synchronously, permit and all, so the connection stays counted
+        // against `max_connections` while it sits idle instead of silently freeing
+        // its slot for a brand-new connection to claim.
+        self.pool
+            .idle
+            .lock()
+            .expect("pool idle mutex poisoned")
+            .entry(self.key.clone())
+            .or_default()
+            .push_back(Idle { connection, permit, since: Instant::now() });
+    }
+}
+
+/// The process-wide pool backing [`pooled`], lazily created from whichever `config`
+/// reaches [`OnceLock::get_or_init`] first.
+///
+/// **`config` is only honored on the very first call in the process.** Every
+/// subsequent call's `config` - different `max_connections`, different
+/// `idle_timeout` - is discarded in favor of the pool that already exists;
+/// a mismatch between what a caller asked for and what the singleton was actually
+/// built with is logged via `warn!` rather than left for the caller to discover the
+/// hard way. If your application needs distinct pools with independent
+/// configuration, build and hold your own [`PatchworkPool`] instances instead of
+/// going through [`Patchwork::pooled`](crate::Patchwork::pooled).
+fn global_pool(config: PoolConfig) -> Arc<PatchworkPool> {
+    static POOL: OnceLock<Arc<PatchworkPool>> = OnceLock::new();
+    let pool = POOL.get_or_init(|| Arc::new(PatchworkPool::new(config)));
+
+    if pool.inner.config != config {
+        warn!(
+            requested = ?config,
+            actual = ?pool.inner.config,
+            "Patchwork::pooled called with a config that differs from the process-wide \
+             pool's existing config; the existing config wins"
+        );
+    }
+
+    pool.clone()
+}
+
+/// Acquire a connection from the process-wide pool.
+///
+/// See [`global_pool`] for the important caveat that `config` is only applied the
+/// first time this is called in the process.
+pub(crate) async fn pooled(
+    component: impl ConnectTo<Client> + PoolKey + 'static,
+    config: PoolConfig,
+) -> Result<Pooled, Error> {
+    global_pool(config).acquire(component).await
+}
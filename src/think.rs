@@ -0,0 +1,236 @@
+//! Builder for composing and running a single LLM "think" block.
+
+use std::fmt::Write as _;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use sacp::{
+    Agent, ConnectionTo,
+    role::{HasPeer, Role},
+    schema::{PromptRequest, SessionUpdate},
+};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::{wrappers::ReceiverStream, Stream, StreamExt};
+use tracing::instrument;
+
+use crate::determinishtic::LastActivity;
+use crate::{Error, WireEvent};
+
+/// One event observed while a think block runs via [`ThinkBuilder::stream`].
+#[derive(Debug)]
+pub enum ThinkEvent<Output> {
+    /// A partial chunk of assistant text.
+    Text(String),
+    /// A registered tool closure was invoked.
+    ToolCall { name: String, args: serde_json::Value },
+    /// A registered tool closure returned.
+    ToolResult { name: String, result: serde_json::Value },
+    /// The agent called `return_result`; always the final event.
+    Result(Output),
+    /// The think block failed - a connection error, the agent never calling
+    /// `return_result`, or a response that didn't deserialize into `Output`. Always
+    /// the final event; the stream ends immediately after.
+    Error(String),
+}
+
+/// Builds up a prompt for a single think block and runs it against the connected agent.
+///
+/// Created via [`Determinishtic::think`](crate::Determinishtic::think) or
+/// [`Patchwork::think`](crate::Patchwork::think). The `'bound` lifetime ties the
+/// builder to any borrowed values passed to [`display`](Self::display).
+pub struct ThinkBuilder<'bound, Output, R: Role = Agent>
+where
+    R: HasPeer<Agent>,
+{
+    cx: ConnectionTo<R>,
+    prompt: String,
+    events: broadcast::Sender<WireEvent>,
+    activity: Arc<LastActivity>,
+    _bound: PhantomData<&'bound ()>,
+    _output: PhantomData<fn() -> Output>,
+}
+
+impl<'bound, Output, R: Role> ThinkBuilder<'bound, Output, R>
+where
+    R: HasPeer<Agent>,
+    Output: Send + JsonSchema + DeserializeOwned + 'static,
+{
+    pub(crate) fn new(
+        cx: ConnectionTo<R>,
+        events: broadcast::Sender<WireEvent>,
+        activity: Arc<LastActivity>,
+    ) -> Self {
+        Self { cx, prompt: String::new(), events, activity, _bound: PhantomData, _output: PhantomData }
+    }
+
+    /// Append literal text to the prompt.
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.prompt.push_str(&text.into());
+        self
+    }
+
+    /// Append the `Display` representation of a value to the prompt.
+    pub fn display(mut self, value: &'bound impl std::fmt::Display) -> Self {
+        let _ = write!(self.prompt, "{value}");
+        self
+    }
+
+    /// Send the composed prompt and resolve once the agent calls `return_result`.
+    ///
+    /// Also drains `session_updates()` for the duration of the call and forwards
+    /// assistant text and tool dispatches to [`subscribe`](crate::Determinishtic::subscribe)
+    /// as [`WireEvent`]s, same as [`stream`](Self::stream) - it just discards the
+    /// typed [`ThinkEvent`]s rather than yielding them, since `run` only returns the
+    /// final `Output`.
+    #[instrument(name = "ThinkBuilder::run", skip_all)]
+    pub async fn run(self) -> Result<Output, Error> {
+        self.activity.touch();
+        let _ = self.events.send(WireEvent::PromptSent { prompt: self.prompt.clone() });
+
+        // See the FIXME on `stream`'s `session_updates()` call about unconfirmed
+        // cross-request correlation.
+        let mut updates = self.cx.session_updates();
+        let response = self.cx.send_request(PromptRequest::new(self.prompt)).block_task();
+        tokio::pin!(response);
+
+        let response = loop {
+            tokio::select! {
+                response = &mut response => break response?,
+                update = updates.next() => {
+                    let Some(update) = update else {
+                        // No more updates will arrive; just wait out the response.
+                        break (&mut response).await?;
+                    };
+                    self.activity.touch();
+                    if let Some(event) = think_event::<Output>(update) {
+                        match &event {
+                            ThinkEvent::Text(text) => {
+                                let _ = self.events.send(WireEvent::AssistantText(text.clone()));
+                            }
+                            ThinkEvent::ToolCall { name, .. } => {
+                                let _ = self.events.send(WireEvent::ToolDispatched { name: name.clone() });
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        };
+
+        self.activity.touch();
+        let raw = response.return_result().ok_or(Error::NoResult)?;
+
+        match serde_json::from_value(raw) {
+            Ok(output) => Ok(output),
+            Err(err) => {
+                let _ = self
+                    .events
+                    .send(WireEvent::DeserializationFailed { error: err.to_string() });
+                Err(Error::from(err))
+            }
+        }
+    }
+
+    /// Stream incremental events as the agent works through the prompt, finishing
+    /// with a [`ThinkEvent::Result`] once `return_result` is received.
+    ///
+    /// Unlike [`run`](Self::run), this yields partial assistant text and tool
+    /// activity to the caller as they happen, so UIs can render progressively and
+    /// long tool chains become observable - `run` forwards the same traffic to
+    /// [`subscribe`](crate::Determinishtic::subscribe) but discards it otherwise.
+    #[instrument(name = "ThinkBuilder::stream", skip_all)]
+    pub fn stream(self) -> impl Stream<Item = ThinkEvent<Output>> + Send + 'bound
+    where
+        Output: Send + 'bound,
+    {
+        let (tx, rx) = mpsc::channel(64);
+        let cx = self.cx;
+        let prompt = self.prompt;
+        let events = self.events;
+        let activity = self.activity;
+
+        activity.touch();
+        let _ = events.send(WireEvent::PromptSent { prompt: prompt.clone() });
+
+        tokio::spawn(async move {
+            // FIXME: unconfirmed against sacp's actual semantics - if
+            // `session_updates()` is connection-scoped rather than scoped to this
+            // call's request, two concurrent `think()` calls against the same
+            // connection (e.g. a `.stream()` overlapping another `.stream()` or a
+            // `.run()`) would cross-deliver each other's updates here. Filter on
+            // whatever id ties an update to its originating request once that's
+            // settled.
+            let mut updates = cx.session_updates();
+            let response = cx.send_request(PromptRequest::new(prompt)).block_task();
+            tokio::pin!(response);
+
+            let mut updates_open = true;
+            loop {
+                tokio::select! {
+                    update = updates.next(), if updates_open => {
+                        let Some(update) = update else {
+                            // No more updates will arrive; keep waiting on the response.
+                            updates_open = false;
+                            continue;
+                        };
+                        activity.touch();
+                        if let Some(event) = think_event(update) {
+                            match &event {
+                                ThinkEvent::Text(text) => {
+                                    let _ = events.send(WireEvent::AssistantText(text.clone()));
+                                }
+                                ThinkEvent::ToolCall { name, .. } => {
+                                    let _ = events.send(WireEvent::ToolDispatched { name: name.clone() });
+                                }
+                                _ => {}
+                            }
+                            if tx.send(event).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    response = &mut response => {
+                        activity.touch();
+                        match response {
+                            Ok(response) => match response.return_result() {
+                                Some(raw) => match serde_json::from_value(raw) {
+                                    Ok(output) => {
+                                        let _ = tx.send(ThinkEvent::Result(output)).await;
+                                    }
+                                    Err(err) => {
+                                        let _ = events.send(WireEvent::DeserializationFailed {
+                                            error: err.to_string(),
+                                        });
+                                        let _ = tx.send(ThinkEvent::Error(err.to_string())).await;
+                                    }
+                                },
+                                None => {
+                                    let _ = tx.send(ThinkEvent::Error(Error::NoResult.to_string())).await;
+                                }
+                            },
+                            Err(err) => {
+                                let _ = tx.send(ThinkEvent::Error(err.to_string())).await;
+                            }
+                        }
+                        break;
+                    }
+                }
+            }
+        });
+
+        ReceiverStream::new(rx)
+    }
+}
+
+fn think_event<Output>(update: SessionUpdate) -> Option<ThinkEvent<Output>> {
+    match update {
+        SessionUpdate::AgentMessageChunk { text, .. } => Some(ThinkEvent::Text(text)),
+        SessionUpdate::ToolCall { name, args, .. } => Some(ThinkEvent::ToolCall { name, args }),
+        SessionUpdate::ToolCallResult { name, result, .. } => {
+            Some(ThinkEvent::ToolResult { name, result })
+        }
+        _ => None,
+    }
+}